@@ -0,0 +1,59 @@
+use lazy_static::lazy_static;
+use x86_64::instructions::segmentation::{Segment, CS};
+use x86_64::instructions::tables::load_tss;
+use x86_64::structures::gdt::{Descriptor, GlobalDescriptorTable, SegmentSelector};
+use x86_64::structures::tss::TaskStateSegment;
+use x86_64::VirtAddr;
+
+/// IST index used for the double-fault handler's dedicated stack (see
+/// `TSS` below). Keeping the double fault on its own stack means a
+/// stack-overflow-triggered double fault doesn't just double-fault again.
+pub const DOUBLE_FAULT_IST_INDEX: u16 = 0;
+
+const STACK_SIZE: usize = 4096 * 5;
+
+lazy_static! {
+    static ref TSS: TaskStateSegment = {
+        let mut tss = TaskStateSegment::new();
+        tss.interrupt_stack_table[DOUBLE_FAULT_IST_INDEX as usize] = {
+            static mut STACK: [u8; STACK_SIZE] = [0; STACK_SIZE];
+
+            // This stack has no guard page, so overflowing it corrupts
+            // whatever memory comes after - acceptable for this IST slot
+            // since it's only ever used by the double-fault handler.
+            let stack_start = VirtAddr::from_ptr(unsafe { &STACK });
+            stack_start + STACK_SIZE as u64
+        };
+        tss
+    };
+}
+
+struct Selectors {
+    code_selector: SegmentSelector,
+    tss_selector: SegmentSelector,
+}
+
+lazy_static! {
+    static ref GDT: (GlobalDescriptorTable, Selectors) = {
+        let mut gdt = GlobalDescriptorTable::new();
+        let code_selector = gdt.add_entry(Descriptor::kernel_code_segment());
+        let tss_selector = gdt.add_entry(Descriptor::tss_segment(&TSS));
+        (
+            gdt,
+            Selectors {
+                code_selector,
+                tss_selector,
+            },
+        )
+    };
+}
+
+/// Load the GDT and TSS. Must run before `interrupts::init_idt()` so the
+/// double-fault entry's IST index actually resolves to a loaded stack.
+pub fn init() {
+    GDT.0.load();
+    unsafe {
+        CS::set_reg(GDT.1.code_selector);
+        load_tss(GDT.1.tss_selector);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::gdt;
+use crate::{println, serial_println};
+use core::sync::atomic::{AtomicBool, Ordering};
+use lazy_static::lazy_static;
+use pic8259::ChainedPics;
+use spin::Mutex;
+use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame};
+
+/// The two 8259 PICs are chained, with the primary remapped to vector
+/// `PIC_1_OFFSET` so its IRQs don't collide with CPU exceptions (0-31).
+pub const PIC_1_OFFSET: u8 = 32;
+pub const PIC_2_OFFSET: u8 = PIC_1_OFFSET + 8;
+
+pub static PICS: Mutex<ChainedPics> =
+    Mutex::new(unsafe { ChainedPics::new(PIC_1_OFFSET, PIC_2_OFFSET) });
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum InterruptIndex {
+    Keyboard = PIC_1_OFFSET + 1,
+}
+
+impl InterruptIndex {
+    fn as_usize(self) -> usize {
+        self as u8 as usize
+    }
+}
+
+lazy_static! {
+    static ref IDT: InterruptDescriptorTable = {
+        let mut idt = InterruptDescriptorTable::new();
+        idt.breakpoint.set_handler_fn(breakpoint_handler);
+        unsafe {
+            idt.double_fault
+                .set_handler_fn(double_fault_handler)
+                .set_stack_index(gdt::DOUBLE_FAULT_IST_INDEX);
+        }
+        idt[InterruptIndex::Keyboard.as_usize()].set_handler_fn(keyboard_interrupt_handler);
+        idt
+    };
+}
+
+/// Build and load the IDT. Must run before interrupts are enabled (see
+/// `crate::init`).
+pub fn init_idt() {
+    IDT.load();
+}
+
+/// Set by `breakpoint_handler` so `test_breakpoint_exception` can confirm
+/// the handler actually ran, not just that `int3` didn't crash the kernel.
+static BREAKPOINT_HIT: AtomicBool = AtomicBool::new(false);
+
+extern "x86-interrupt" fn breakpoint_handler(stack_frame: InterruptStackFrame) {
+    BREAKPOINT_HIT.store(true, Ordering::SeqCst);
+    serial_println!("EXCEPTION: BREAKPOINT\n{:#?}", stack_frame);
+    println!("EXCEPTION: BREAKPOINT\n{:#?}", stack_frame);
+}
+
+extern "x86-interrupt" fn double_fault_handler(
+    stack_frame: InterruptStackFrame,
+    _error_code: u64,
+) -> ! {
+    panic!("EXCEPTION: DOUBLE FAULT\n{:#?}", stack_frame);
+}
+
+extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    use x86_64::instructions::port::Port;
+
+    // Always read the scancode, even if `add_scancode` ends up dropping
+    // it, so the controller is free to deliver the next interrupt.
+    let mut port = Port::new(0x60);
+    let scancode: u8 = unsafe { port.read() };
+    crate::keyboard::add_scancode(scancode);
+
+    unsafe {
+        PICS.lock()
+            .notify_end_of_interrupt(InterruptIndex::Keyboard as u8);
+    }
+}
+
+#[test_case]
+fn test_breakpoint_exception() {
+    BREAKPOINT_HIT.store(false, Ordering::SeqCst);
+    x86_64::instructions::interrupts::int3();
+    assert!(BREAKPOINT_HIT.load(Ordering::SeqCst));
+}
@@ -0,0 +1,166 @@
+use spin::Mutex;
+use x86_64::instructions::hlt;
+
+const BUFFER_SIZE: usize = 128;
+
+const LEFT_SHIFT_MAKE: u8 = 0x2a;
+const RIGHT_SHIFT_MAKE: u8 = 0x36;
+const LEFT_SHIFT_BREAK: u8 = 0xaa;
+const RIGHT_SHIFT_BREAK: u8 = 0xb6;
+const CAPS_LOCK_MAKE: u8 = 0x3a;
+const BREAK_BIT: u8 = 0x80;
+
+/// Fixed-size ring buffer of decoded ASCII bytes, written by the keyboard
+/// interrupt handler and drained by `try_read_char`/`read_char`.
+struct RingBuffer {
+    buf: [u8; BUFFER_SIZE],
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl RingBuffer {
+    const fn new() -> Self {
+        RingBuffer {
+            buf: [0; BUFFER_SIZE],
+            head: 0,
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    /// Drops the byte if the buffer is full; the caller (the interrupt
+    /// handler) must still drain the controller so it keeps delivering IRQs.
+    fn push(&mut self, byte: u8) {
+        if self.len == BUFFER_SIZE {
+            return;
+        }
+        self.buf[self.tail] = byte;
+        self.tail = (self.tail + 1) % BUFFER_SIZE;
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let byte = self.buf[self.head];
+        self.head = (self.head + 1) % BUFFER_SIZE;
+        self.len -= 1;
+        Some(byte)
+    }
+}
+
+static BUFFER: Mutex<RingBuffer> = Mutex::new(RingBuffer::new());
+static SHIFT_HELD: Mutex<bool> = Mutex::new(false);
+static CAPS_LOCK: Mutex<bool> = Mutex::new(false);
+
+/// Decode a scancode-set-1 make code into its lower/upper-case ASCII byte,
+/// or `None` for codes we don't have a character mapping for (function
+/// keys, arrows, etc).
+fn decode(scancode: u8, shift: bool) -> Option<u8> {
+    let (lower, upper): (u8, u8) = match scancode {
+        0x02 => (b'1', b'!'),
+        0x03 => (b'2', b'@'),
+        0x04 => (b'3', b'#'),
+        0x05 => (b'4', b'$'),
+        0x06 => (b'5', b'%'),
+        0x07 => (b'6', b'^'),
+        0x08 => (b'7', b'&'),
+        0x09 => (b'8', b'*'),
+        0x0a => (b'9', b'('),
+        0x0b => (b'0', b')'),
+        0x0c => (b'-', b'_'),
+        0x0d => (b'=', b'+'),
+        0x0e => (8, 8),      // backspace
+        0x0f => (b'\t', b'\t'),
+        0x10 => (b'q', b'Q'),
+        0x11 => (b'w', b'W'),
+        0x12 => (b'e', b'E'),
+        0x13 => (b'r', b'R'),
+        0x14 => (b't', b'T'),
+        0x15 => (b'y', b'Y'),
+        0x16 => (b'u', b'U'),
+        0x17 => (b'i', b'I'),
+        0x18 => (b'o', b'O'),
+        0x19 => (b'p', b'P'),
+        0x1a => (b'[', b'{'),
+        0x1b => (b']', b'}'),
+        0x1c => (b'\n', b'\n'), // enter
+        0x1e => (b'a', b'A'),
+        0x1f => (b's', b'S'),
+        0x20 => (b'd', b'D'),
+        0x21 => (b'f', b'F'),
+        0x22 => (b'g', b'G'),
+        0x23 => (b'h', b'H'),
+        0x24 => (b'j', b'J'),
+        0x25 => (b'k', b'K'),
+        0x26 => (b'l', b'L'),
+        0x27 => (b';', b':'),
+        0x28 => (b'\'', b'"'),
+        0x29 => (b'`', b'~'),
+        0x2b => (b'\\', b'|'),
+        0x2c => (b'z', b'Z'),
+        0x2d => (b'x', b'X'),
+        0x2e => (b'c', b'C'),
+        0x2f => (b'v', b'V'),
+        0x30 => (b'b', b'B'),
+        0x31 => (b'n', b'N'),
+        0x32 => (b'm', b'M'),
+        0x33 => (b',', b'<'),
+        0x34 => (b'.', b'>'),
+        0x35 => (b'/', b'?'),
+        0x39 => (b' ', b' '),
+        _ => return None,
+    };
+    Some(if shift { upper } else { lower })
+}
+
+fn is_letter(scancode: u8) -> bool {
+    matches!(scancode, 0x10..=0x19 | 0x1e..=0x26 | 0x2c..=0x32)
+}
+
+/// Feed one scancode byte (as read from port `0x60`) into the decoder.
+/// Called from the keyboard interrupt handler for every scancode, make or
+/// break, so shift/caps state and the ring buffer stay in sync even if
+/// the buffer itself is full.
+pub fn add_scancode(scancode: u8) {
+    match scancode {
+        LEFT_SHIFT_MAKE | RIGHT_SHIFT_MAKE => *SHIFT_HELD.lock() = true,
+        LEFT_SHIFT_BREAK | RIGHT_SHIFT_BREAK => *SHIFT_HELD.lock() = false,
+        CAPS_LOCK_MAKE => {
+            let mut caps = CAPS_LOCK.lock();
+            *caps = !*caps;
+        }
+        code if code & BREAK_BIT == 0 => {
+            let shift = *SHIFT_HELD.lock();
+            let effective_shift = if is_letter(code) {
+                shift ^ *CAPS_LOCK.lock()
+            } else {
+                shift
+            };
+            if let Some(ascii) = decode(code, effective_shift) {
+                BUFFER.lock().push(ascii);
+            }
+        }
+        _ => {} // other break codes carry no character of their own
+    }
+}
+
+/// Pop the oldest decoded byte, if any, without blocking.
+pub fn try_read_char() -> Option<u8> {
+    // Interrupts stay off while the lock is held so the keyboard handler
+    // can't spin on it forever.
+    x86_64::instructions::interrupts::without_interrupts(|| BUFFER.lock().pop())
+}
+
+/// Block until a decoded byte is available, halting the CPU between polls
+/// so we're not busy-waiting.
+pub fn read_char() -> u8 {
+    loop {
+        if let Some(byte) = try_read_char() {
+            return byte;
+        }
+        hlt();
+    }
+}
@@ -1,14 +1,29 @@
 #![no_std]
 #![cfg_attr(test, no_main)]
 #![feature(custom_test_frameworks)]
+#![feature(abi_x86_interrupt)]
 #![test_runner(crate::test_runner)]
 #![reexport_test_harness_main = "test_main"]
 
+pub mod gdt;
+pub mod interrupts;
+pub mod keyboard;
+pub mod panic_screen;
 pub mod serial_interface;
 pub mod vga_interface;
 
 use core::panic::PanicInfo;
 
+/// Bring up the GDT/TSS and IDT, remap and initialize the PICs and enable
+/// interrupts.
+/// Must be called once before any code relies on exception or IRQ handling.
+pub fn init() {
+    gdt::init();
+    interrupts::init_idt();
+    unsafe { interrupts::PICS.lock().initialize() };
+    x86_64::instructions::interrupts::enable();
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
 pub enum QemuExitCode {
@@ -58,6 +73,7 @@ pub fn test_panic(info: &PanicInfo) -> ! {
 #[cfg(test)]
 #[no_mangle]
 pub extern "C" fn _start() -> ! {
+    init();
     test_main();
     loop {}
 }
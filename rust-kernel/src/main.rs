@@ -5,10 +5,12 @@
 #![reexport_test_harness_main = "test_main"]
 
 use core::panic::PanicInfo;
-use rust_kernel::{print, println, serial_println};
+use rust_kernel::{println, serial_println};
 
 #[no_mangle]
 pub extern "C" fn _start() -> ! {
+    rust_kernel::init();
+
     #[cfg(test)]
     test_main();
 
@@ -21,8 +23,7 @@ pub extern "C" fn _start() -> ! {
 #[cfg(not(test))]
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
-    print!("\n-------- PANIC --------\n");
-    println!("{}\n-----------------------", info);
+    rust_kernel::panic_screen::show_panic(info);
     loop {}
 }
 
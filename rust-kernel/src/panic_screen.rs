@@ -0,0 +1,57 @@
+use crate::vga_interface::{Color, PRINTER};
+use core::fmt;
+use core::panic::PanicInfo;
+
+const BUFFER_CAPACITY: usize = 2048;
+
+/// A `core::fmt::Write` implementation over a fixed stack buffer, so the
+/// panic handler can format the panic message without allocating. Once the
+/// buffer is full, `write_str` fails instead of overflowing it.
+struct BoundedWriter {
+    buf: [u8; BUFFER_CAPACITY],
+    len: usize,
+}
+
+impl BoundedWriter {
+    const fn new() -> Self {
+        BoundedWriter {
+            buf: [0; BUFFER_CAPACITY],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("<panic message is not valid utf-8>")
+    }
+}
+
+impl fmt::Write for BoundedWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes = s.as_bytes();
+        if self.len + bytes.len() > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+}
+
+/// Render `info` to the full screen: a white-on-red background with a
+/// centered header and the panic message below it. Formatting the message
+/// is best-effort - it stops at the first piece that doesn't fit in the
+/// bounded buffer rather than risk panicking again while handling a panic.
+/// Only the first 80 bytes of the message are shown, on a single row;
+/// line breaks in the message are not honored.
+pub fn show_panic(info: &PanicInfo) {
+    use core::fmt::Write;
+
+    let mut writer = BoundedWriter::new();
+    let _ = write!(writer, "{}", info);
+
+    let mut printer = PRINTER.lock();
+    printer.set_color(Color::White, Color::Red);
+    printer.fill_screen();
+    printer.print_centered(1, "KERNEL PANIC");
+    printer.print_centered(3, writer.as_str());
+}
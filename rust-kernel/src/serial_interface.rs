@@ -1,9 +1,16 @@
 use lazy_static::lazy_static;
 use spin::Mutex;
 use uart_16550::SerialPort;
+use x86_64::instructions::port::Port;
 
 const SERIAL_PORT_ADDRESS: u16 = 0x3f8;
 
+// The UART's line status register sits 5 ports above the data port; bit 0
+// is set once a received byte is waiting to be read. `uart_16550` doesn't
+// expose this for a non-blocking read, so we poll it ourselves.
+const LINE_STATUS_OFFSET: u16 = 5;
+const LINE_STATUS_DATA_READY: u8 = 0x01;
+
 lazy_static! {
     pub static ref SERIAL: Mutex<SerialPort> = {
         let mut serial = unsafe { SerialPort::new(SERIAL_PORT_ADDRESS) };
@@ -12,6 +19,56 @@ lazy_static! {
     };
 }
 
+/// Block until a byte arrives on the serial line, then return it.
+pub fn read_byte() -> u8 {
+    SERIAL.lock().receive()
+}
+
+/// Return the next received byte without blocking, or `None` if nothing
+/// has arrived yet.
+pub fn try_read_byte() -> Option<u8> {
+    let mut status_port: Port<u8> = Port::new(SERIAL_PORT_ADDRESS + LINE_STATUS_OFFSET);
+    let status = unsafe { status_port.read() };
+
+    if status & LINE_STATUS_DATA_READY != 0 {
+        Some(read_byte())
+    } else {
+        None
+    }
+}
+
+/// Read a line from the serial port into `buf`, stopping at `\r` or `\n`
+/// (which are not stored), handling backspace, and echoing every stored
+/// character back over the port. Returns the number of bytes written to
+/// `buf`; input past `buf`'s capacity is dropped without being echoed.
+pub fn serial_read_line(buf: &mut [u8]) -> usize {
+    let mut len = 0;
+
+    loop {
+        match read_byte() {
+            b'\r' | b'\n' => {
+                crate::serial_print!("\r\n");
+                break;
+            }
+            0x08 | 0x7f => {
+                // Backspace/delete: erase the last echoed character.
+                if len > 0 {
+                    len -= 1;
+                    crate::serial_print!("\u{8} \u{8}");
+                }
+            }
+            byte if len < buf.len() => {
+                buf[len] = byte;
+                len += 1;
+                SERIAL.lock().send(byte);
+            }
+            _ => {} // buffer full, drop the byte but keep reading the line
+        }
+    }
+
+    len
+}
+
 /*
 ------------------------------------ Macros ------------------------------------
 More info: https://os.phil-opp.com/testing/#serial-port
@@ -38,3 +95,50 @@ pub fn _print(args: core::fmt::Arguments) {
         .write_fmt(args)
         .expect("Printing to serial failed");
 }
+
+const MONITOR_LINE_CAPACITY: usize = 128;
+const MEM_DUMP_LEN: usize = 16;
+
+/// A tiny command loop over the serial line, so a host attached to QEMU's
+/// serial port can drive the kernel without a display. Supports `help`,
+/// `mem <hex-addr>` and `exit`; never returns.
+pub fn monitor() -> ! {
+    serial_println!("Serial monitor ready. Type 'help' for commands.");
+
+    let mut buf = [0u8; MONITOR_LINE_CAPACITY];
+    loop {
+        serial_print!("> ");
+        let len = serial_read_line(&mut buf);
+        let line = core::str::from_utf8(&buf[..len]).unwrap_or("");
+        let mut parts = line.split_whitespace();
+
+        match parts.next() {
+            Some("help") => serial_println!("Commands: help, mem <hex-addr>, exit"),
+            Some("mem") => match parts.next().and_then(parse_hex_addr) {
+                Some(addr) => dump_mem(addr),
+                None => serial_println!("usage: mem <hex-addr>"),
+            },
+            Some("exit") => crate::exit_qemu(crate::QemuExitCode::Success),
+            Some(other) => serial_println!("unknown command: {}", other),
+            None => {}
+        }
+    }
+}
+
+fn parse_hex_addr(s: &str) -> Option<usize> {
+    usize::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+}
+
+/// Print `MEM_DUMP_LEN` bytes starting at `addr` as hex.
+///
+/// ### Safety
+/// This reads raw memory the caller typed in over serial; an invalid
+/// address will fault like any other bad dereference.
+fn dump_mem(addr: usize) {
+    serial_print!("{:#010x}: ", addr);
+    for offset in 0..MEM_DUMP_LEN {
+        let byte = unsafe { *((addr + offset) as *const u8) };
+        serial_print!("{:02x} ", byte);
+    }
+    serial_println!();
+}
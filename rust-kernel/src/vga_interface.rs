@@ -2,6 +2,7 @@ use core::fmt;
 use lazy_static::lazy_static;
 use spin::Mutex;
 use volatile::Volatile;
+use x86_64::instructions::port::Port;
 
 const VGA_BUFFER_ADDRESS: usize = 0xb8000;
 const VGA_BUFFER_HEIGTH: usize = 25;
@@ -10,6 +11,45 @@ const VGA_BUFFER_WIDTH: usize = 80;
 /// Printed when an unknown character is in the buffer. It's a ■.
 const UNPRINTABLE_CHAR: u8 = 0xfe;
 
+// VGA CRT controller I/O ports, used to move and (de)configure the
+// blinking hardware cursor. See https://wiki.osdev.org/Text_Mode_Cursor.
+const CRTC_INDEX_PORT: u16 = 0x3d4;
+const CRTC_DATA_PORT: u16 = 0x3d5;
+const CRTC_CURSOR_START_REGISTER: u8 = 0x0a;
+const CRTC_CURSOR_END_REGISTER: u8 = 0x0b;
+const CRTC_CURSOR_LOCATION_LOW_REGISTER: u8 = 0x0f;
+const CRTC_CURSOR_LOCATION_HIGH_REGISTER: u8 = 0x0e;
+const CURSOR_DISABLED_START_SCANLINE: u8 = 0x20;
+
+/// Show the blinking hardware cursor as a block spanning scanlines
+/// `start_scanline` to `end_scanline` (0-15 for the standard 16-scanline
+/// text mode glyph).
+pub fn enable_cursor(start_scanline: u8, end_scanline: u8) {
+    let mut index_port: Port<u8> = Port::new(CRTC_INDEX_PORT);
+    let mut data_port: Port<u8> = Port::new(CRTC_DATA_PORT);
+
+    unsafe {
+        index_port.write(CRTC_CURSOR_START_REGISTER);
+        let current = data_port.read();
+        data_port.write((current & 0xc0) | start_scanline);
+
+        index_port.write(CRTC_CURSOR_END_REGISTER);
+        let current = data_port.read();
+        data_port.write((current & 0xe0) | end_scanline);
+    }
+}
+
+/// Hide the blinking hardware cursor.
+pub fn disable_cursor() {
+    let mut index_port: Port<u8> = Port::new(CRTC_INDEX_PORT);
+    let mut data_port: Port<u8> = Port::new(CRTC_DATA_PORT);
+
+    unsafe {
+        index_port.write(CRTC_CURSOR_START_REGISTER);
+        data_port.write(CURSOR_DISABLED_START_SCANLINE);
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -69,6 +109,7 @@ struct ScreenBuffer {
 
 pub struct Printer {
     col_pos: usize,
+    row_pos: usize,
     current_color_code: ColorCode,
     buffer: &'static mut ScreenBuffer, // Permanent lifetime ('static)
 }
@@ -91,7 +132,7 @@ impl Printer {
                     self.newline();
                 }
 
-                let row = VGA_BUFFER_HEIGTH - 1;
+                let row = self.row_pos;
                 let col = self.col_pos;
                 let color_code = self.current_color_code;
 
@@ -103,6 +144,7 @@ impl Printer {
 
                 // Move to the next position in the buffer
                 self.col_pos += 1;
+                self.update_cursor();
             }
         }
     }
@@ -142,10 +184,76 @@ impl Printer {
         }
     }
 
+    /// Permanently change the color used for subsequent writes.
+    ///
+    /// ### Arguments
+    /// * `fg` - The foreground color
+    /// * `bg` - The background color
+    pub fn set_color(&mut self, fg: Color, bg: Color) {
+        self.current_color_code = ColorCode::new(fg, bg);
+    }
+
+    /// Run `f` with the color temporarily set to `fg`/`bg`, restoring the
+    /// previous color afterwards regardless of what `f` itself does to it.
+    ///
+    /// ### Arguments
+    /// * `fg` - The foreground color to use while `f` runs
+    /// * `bg` - The background color to use while `f` runs
+    /// * `f` - The closure to run with the scoped color
+    pub fn with_color(&mut self, fg: Color, bg: Color, f: impl FnOnce(&mut Printer)) {
+        let previous = self.current_color_code;
+        self.set_color(fg, bg);
+        f(self);
+        self.current_color_code = previous;
+    }
+
+    /// Blank every row on the screen using the current color, e.g. to turn
+    /// the whole display into a solid background before printing over it.
+    pub fn fill_screen(&mut self) {
+        for row in 0..VGA_BUFFER_HEIGTH {
+            self.clear_row(row);
+        }
+    }
+
+    /// Write `s` into `row`, centered horizontally, padding the rest of the
+    /// row with spaces in the current color. `s` is truncated to
+    /// `VGA_BUFFER_WIDTH` bytes if it's wider than the screen.
+    ///
+    /// ### Arguments
+    /// * `row` - The row to write into
+    /// * `s` - The string to center
+    pub fn print_centered(&mut self, row: usize, s: &str) {
+        let len = s.len().min(VGA_BUFFER_WIDTH);
+        let padding = (VGA_BUFFER_WIDTH - len) / 2;
+
+        for col in 0..VGA_BUFFER_WIDTH {
+            let ascii_code = if col < padding || col >= padding + len {
+                b' '
+            } else {
+                s.as_bytes()[col - padding]
+            };
+
+            self.buffer.chars[row][col].write(PrintableChar {
+                ascii_code,
+                color_code: self.current_color_code,
+            });
+        }
+    }
+
     /// When we have to move to the next line (either the current line is full
-    /// or the current char is a `\n`), we move all characters one row above,
-    /// and we clear the current line.
+    /// or the current char is a `\n`), we move down to the next row. Only
+    /// once `row_pos` reaches the bottom of the screen do we scroll
+    /// everything up by one row, so normal top-to-bottom logging doesn't
+    /// pay the O(rows x cols) scroll cost on every line.
     fn newline(&mut self) {
+        self.col_pos = 0;
+
+        if self.row_pos < VGA_BUFFER_HEIGTH - 1 {
+            self.row_pos += 1;
+            self.update_cursor();
+            return;
+        }
+
         // Note: Row 0 is omitted bcs. it's off the screen.
         for row in 1..VGA_BUFFER_HEIGTH {
             for col in 0..VGA_BUFFER_WIDTH {
@@ -155,7 +263,31 @@ impl Printer {
         }
 
         self.clear_row(VGA_BUFFER_HEIGTH - 1);
+        self.update_cursor();
+    }
+
+    /// Blank every row and reset the cursor to the top-left of the screen.
+    pub fn clear_screen(&mut self) {
+        self.fill_screen();
+        self.row_pos = 0;
         self.col_pos = 0;
+        self.update_cursor();
+    }
+
+    /// Move the blinking hardware cursor to the current write position.
+    fn update_cursor(&self) {
+        let pos = self.row_pos * VGA_BUFFER_WIDTH + self.col_pos;
+
+        let mut index_port: Port<u8> = Port::new(CRTC_INDEX_PORT);
+        let mut data_port: Port<u8> = Port::new(CRTC_DATA_PORT);
+
+        unsafe {
+            index_port.write(CRTC_CURSOR_LOCATION_LOW_REGISTER);
+            data_port.write((pos & 0xff) as u8);
+
+            index_port.write(CRTC_CURSOR_LOCATION_HIGH_REGISTER);
+            data_port.write(((pos >> 8) & 0xff) as u8);
+        }
     }
 }
 
@@ -171,6 +303,7 @@ lazy_static! {
     /// Global `Printer` instance. Used by the macros.
     pub static ref PRINTER: Mutex<Printer> = Mutex::new(Printer {
         col_pos: 0,
+        row_pos: 0,
         current_color_code: ColorCode::new(Color::White, Color::Black),
         buffer: unsafe { &mut *(VGA_BUFFER_ADDRESS as *mut ScreenBuffer) },
     });
@@ -200,3 +333,40 @@ pub fn _print(args: fmt::Arguments) {
     use core::fmt::Write;
     PRINTER.lock().write_fmt(args).unwrap();
 }
+
+#[macro_export]
+/// Print a string to the screen in the given colors, without permanently
+/// changing the global color.
+macro_rules! cprint {
+    ($fg:expr, $bg:expr, $($arg:tt)*) => {
+        $crate::vga_interface::_cprint($fg, $bg, format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+/// Print a string (+ `\n` at the end) to the screen in the given colors,
+/// without permanently changing the global color.
+macro_rules! cprintln {
+    ($fg:expr, $bg:expr) => ($crate::cprint!($fg, $bg, "\n"));
+    ($fg:expr, $bg:expr, $($arg:tt)*) => {
+        $crate::cprint!($fg, $bg, "{}\n", format_args!($($arg)*))
+    };
+}
+
+#[doc(hidden)]
+/// Write to the buffer using the global Printer instance, scoped to the
+/// given foreground/background color.
+pub fn _cprint(fg: Color, bg: Color, args: fmt::Arguments) {
+    use core::fmt::Write;
+    PRINTER
+        .lock()
+        .with_color(fg, bg, |printer| printer.write_fmt(args).unwrap());
+}
+
+#[macro_export]
+/// Blank the screen and reset the cursor to the top-left.
+macro_rules! clear {
+    () => {
+        $crate::vga_interface::PRINTER.lock().clear_screen()
+    };
+}